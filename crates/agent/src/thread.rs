@@ -2818,6 +2818,8 @@ impl Thread {
 
             log::debug!("Calling model.stream_completion, attempt {}", attempt);
 
+            let request_started_at = cx.background_executor().now();
+            let mut first_event_at = None;
             let (mut events, mut error) = match model.stream_completion(request, cx).await {
                 Ok(events) => (events.fuse(), None),
                 Err(err) => (stream::empty().boxed().fuse(), Some(err)),
@@ -2866,6 +2868,9 @@ impl Thread {
                 let Some(first_event) = first_event else {
                     break;
                 };
+                if first_event_at.is_none() {
+                    first_event_at = Some(cx.background_executor().now());
+                }
 
                 // Collect all immediately available events to process as a batch
                 let mut batch = vec![first_event];
@@ -2929,6 +2934,29 @@ impl Thread {
             // that need their own permits.
             drop(events);
 
+            let finished_at = cx.background_executor().now();
+            let usage = this.read_with(cx, |this, _| this.current_request_token_usage)?;
+            let outcome = if cancelled {
+                "was cancelled"
+            } else if error.is_some() {
+                "failed"
+            } else if had_refusal {
+                "was refused"
+            } else {
+                "finished"
+            };
+            log::debug!(
+                "Completion from {} {} after {:?}, attempt {} (time to first event: {:?}, input tokens: {}, output tokens: {})",
+                model.telemetry_id(),
+                outcome,
+                finished_at.duration_since(request_started_at),
+                attempt,
+                first_event_at
+                    .map(|first_event_at| first_event_at.duration_since(request_started_at)),
+                usage.input_tokens,
+                usage.output_tokens,
+            );
+
             // Drop streaming tool input senders that never received their final input.
             // This prevents deadlock when the LLM stream ends (e.g. because of an error)
             // before sending a tool use with `is_input_complete: true`.
@@ -4553,6 +4581,7 @@ impl Thread {
             | PermissionError { .. }
             | NoApiKey { .. }
             | ApiEndpointNotFound { .. }
+            | ModelNotFound { .. }
             | PromptTooLarge { .. } => None,
             // These errors might be transient, so retry them
             SerializeRequest { .. } | BuildRequestBody { .. } | StreamEndedUnexpectedly { .. } => {
@@ -579,6 +579,8 @@ mod tests {
             subagent_model: None,
             inline_assistant_model: None,
             inline_assistant_use_streaming_tools: false,
+            inline_assistant_include_file_path: true,
+            inline_assistant_coalesce_ms: 16,
             commit_message_model: None,
             commit_message_include_project_rules: true,
             commit_message_instructions: None,
@@ -215,6 +215,8 @@ pub struct AgentSettings {
     pub subagent_model: Option<LanguageModelSelection>,
     pub inline_assistant_model: Option<LanguageModelSelection>,
     pub inline_assistant_use_streaming_tools: bool,
+    pub inline_assistant_include_file_path: bool,
+    pub inline_assistant_coalesce_ms: u64,
     pub commit_message_model: Option<LanguageModelSelection>,
     pub commit_message_include_project_rules: bool,
     pub commit_message_instructions: Option<String>,
@@ -765,6 +767,8 @@ impl Settings for AgentSettings {
             inline_assistant_use_streaming_tools: agent
                 .inline_assistant_use_streaming_tools
                 .unwrap_or(true),
+            inline_assistant_include_file_path: agent.inline_assistant_include_file_path.unwrap(),
+            inline_assistant_coalesce_ms: agent.inline_assistant_coalesce_ms.unwrap(),
             commit_message_include_project_rules: agent
                 .commit_message_include_project_rules
                 .unwrap(),
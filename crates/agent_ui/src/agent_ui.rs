@@ -39,6 +39,7 @@ mod unicode_confusables;
 
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Instant;
 
 use ::ui::IconName;
 use agent_client_protocol::schema::v1 as acp;
@@ -47,6 +48,7 @@ use command_palette_hooks::CommandPaletteFilter;
 use editor::{Editor, SelectionEffects, scroll::Autoscroll};
 use feature_flags::FeatureFlagAppExt as _;
 use fs::Fs;
+use futures::StreamExt as _;
 use gpui::{
     Action, App, Context, Entity, ImageSource, ReadGlobal as _, Resource, SharedString, SharedUri,
     TaskExt, Window, actions,
@@ -56,7 +58,8 @@ use language::{
     language_settings::{AllLanguageSettings, EditPredictionProvider},
 };
 use language_model::{
-    ConfiguredModel, LanguageModelId, LanguageModelProviderId, LanguageModelRegistry,
+    ConfiguredModel, LanguageModelCompletionError, LanguageModelId, LanguageModelProviderId,
+    LanguageModelRegistry, LanguageModelRequest, LanguageModelRequestMessage, Role,
 };
 use project::{AgentId, DisableAiSettings};
 use prompt_store::{self, PromptBuilder, rules_to_skills_migration};
@@ -237,6 +240,8 @@ actions!(
         CycleNextInlineAssist,
         /// Cycles to the previous inline assist suggestion.
         CyclePreviousInlineAssist,
+        /// Stops every inline assist that is still generating.
+        StopAllInlineAssists,
         /// Moves focus up in the interface.
         FocusUp,
         /// Moves focus down in the interface.
@@ -255,6 +260,8 @@ actions!(
         LoadThreadFromClipboard,
         /// Reruns the rules-to-skills migration.
         RerunRulesToSkillsMigration,
+        /// Sends a short request to the default model to check that its provider is set up correctly.
+        TestLanguageModelConnection,
         /// Keeps the current suggestion or change.
         Keep,
         /// Rejects the current suggestion or change.
@@ -662,6 +669,14 @@ pub fn init(
                 import_threads_from_other_channels(workspace, cx);
             },
         );
+        workspace.register_action(
+            |workspace: &mut Workspace,
+             _: &TestLanguageModelConnection,
+             _window: &mut Window,
+             cx: &mut Context<Workspace>| {
+                test_language_model_connection(workspace, cx);
+            },
+        );
     })
     .detach();
 
@@ -765,6 +780,73 @@ fn show_rules_to_skills_migration_toast(
     }
 }
 
+/// Identifies the toast reporting the result of [`TestLanguageModelConnection`].
+struct LanguageModelConnectionTestToast;
+
+fn test_language_model_connection(workspace: &mut Workspace, cx: &mut Context<Workspace>) {
+    if DisableAiSettings::get_global(cx).disable_ai {
+        return;
+    }
+    let registry = LanguageModelRegistry::read_global(cx);
+    let configured_model = registry.default_model();
+    if let Some(error) = registry.configuration_error(configured_model.clone(), cx) {
+        workspace.show_toast(connection_test_toast(error.to_string(), false), cx);
+        return;
+    }
+    let Some(ConfiguredModel { model, .. }) = configured_model else {
+        return;
+    };
+
+    let request = LanguageModelRequest {
+        messages: vec![LanguageModelRequestMessage {
+            role: Role::User,
+            content: vec!["Reply with OK.".into()],
+            cache: false,
+            reasoning_details: None,
+        }],
+        ..Default::default()
+    };
+
+    cx.spawn(async move |workspace, cx| {
+        let started_at = Instant::now();
+        let result = async {
+            let mut response = model.stream_completion_text(request, cx).await?;
+            while let Some(chunk) = response.stream.next().await {
+                chunk?;
+            }
+            Ok::<_, LanguageModelCompletionError>(())
+        }
+        .await;
+
+        let model_name = model.name().0;
+        let (message, succeeded) = match result {
+            Ok(()) => (
+                format!(
+                    "{model_name} responded in {} ms",
+                    started_at.elapsed().as_millis()
+                ),
+                true,
+            ),
+            Err(error) => (format!("Failed to reach {model_name}: {error}"), false),
+        };
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(connection_test_toast(message, succeeded), cx)
+            })
+            .ok();
+    })
+    .detach();
+}
+
+/// Successful results hide themselves, while errors stay until dismissed so they can be read.
+fn connection_test_toast(message: String, succeeded: bool) -> workspace::Toast {
+    let toast = workspace::Toast::new(
+        workspace::notifications::NotificationId::unique::<LanguageModelConnectionTestToast>(),
+        message,
+    );
+    if succeeded { toast.autohide() } else { toast }
+}
+
 fn maybe_backfill_editor_layout(fs: Arc<dyn Fs>, is_new_install: bool, cx: &mut App) {
     let kvp = db::kvp::KeyValueStore::global(cx);
     let already_backfilled =
@@ -984,6 +1066,8 @@ mod tests {
             subagent_model: None,
             inline_assistant_model: None,
             inline_assistant_use_streaming_tools: false,
+            inline_assistant_include_file_path: true,
+            inline_assistant_coalesce_ms: 16,
             commit_message_model: None,
             commit_message_include_project_rules: true,
             commit_message_instructions: None,
@@ -1130,6 +1214,49 @@ mod tests {
         });
     }
 
+    #[gpui::test]
+    async fn test_language_model_connection_reports_unconfigured_model(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let store = SettingsStore::test(cx);
+            cx.set_global(store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+            release_channel::init(semver::Version::new(0, 0, 0), cx);
+            AgentSettings::register(cx);
+            DisableAiSettings::register(cx);
+            LanguageModelRegistry::test(cx);
+            LanguageModelRegistry::global(cx)
+                .update(cx, |registry, cx| registry.set_default_model(None, cx));
+        });
+        let fs = fs::FakeFs::new(cx.background_executor.clone());
+        let project = project::Project::test(fs, [], cx).await;
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+        let toast_id =
+            workspace::notifications::NotificationId::unique::<LanguageModelConnectionTestToast>();
+
+        cx.update(|_, cx| {
+            DisableAiSettings::override_global(DisableAiSettings { disable_ai: true }, cx)
+        });
+        let notification_ids = workspace.update(cx, |workspace, cx| {
+            test_language_model_connection(workspace, cx);
+            workspace.notification_ids()
+        });
+        assert!(
+            notification_ids.is_empty(),
+            "nothing is tested with AI disabled"
+        );
+
+        cx.update(|_, cx| {
+            DisableAiSettings::override_global(DisableAiSettings { disable_ai: false }, cx)
+        });
+        let notification_ids = workspace.update(cx, |workspace, cx| {
+            test_language_model_connection(workspace, cx);
+            workspace.notification_ids()
+        });
+        assert_eq!(notification_ids, vec![toast_id]);
+    }
+
     async fn setup_backfill_test(cx: &mut TestAppContext) -> Arc<dyn Fs> {
         let fs = fs::FakeFs::new(cx.background_executor.clone());
         fs.save(
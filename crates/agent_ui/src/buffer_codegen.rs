@@ -13,7 +13,8 @@ use futures::{
 };
 use gpui::{App, AppContext as _, AsyncApp, Context, Entity, EventEmitter, Subscription, Task};
 use language::{
-    Buffer, BufferEditSource, IndentKind, LanguageName, Point, TransactionId, line_diff,
+    Buffer, BufferEditSource, BufferSnapshot, IndentKind, LanguageName, Point, TransactionId,
+    line_diff,
 };
 use language_model::{
     CompletionIntent, LanguageModel, LanguageModelCompletionError, LanguageModelCompletionEvent,
@@ -39,7 +40,7 @@ use std::{
     pin::Pin,
     sync::Arc,
     task::{self, Poll},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use streaming_diff::{CharOperation, LineDiff, LineOperation, StreamingDiff};
 use uuid::Uuid;
@@ -452,6 +453,19 @@ impl CodegenAlternative {
         Ok(())
     }
 
+    /// Returns the path to mention in the prompt, unless disabled by the user
+    /// or the file is private.
+    fn prompt_file_path(buffer: &BufferSnapshot, cx: &App) -> Option<String> {
+        if !AgentSettings::get_global(cx).inline_assistant_include_file_path {
+            return None;
+        }
+        let file = buffer.file()?;
+        if file.is_private() {
+            return None;
+        }
+        Some(file.path().display(file.path_style(cx)).to_string())
+    }
+
     fn build_request_tools(
         &self,
         model: &Arc<dyn LanguageModel>,
@@ -486,10 +500,12 @@ impl CodegenAlternative {
             anyhow::bail!("invalid transformation range");
         };
 
+        let file_path = Self::prompt_file_path(&buffer, cx);
         let system_prompt = self
             .builder
             .generate_inline_transformation_prompt_tools(
                 language_name,
+                file_path.as_deref(),
                 buffer,
                 range.start.0..range.end.0,
             )
@@ -594,11 +610,13 @@ impl CodegenAlternative {
             anyhow::bail!("invalid transformation range");
         };
 
+        let file_path = Self::prompt_file_path(&buffer, cx);
         let prompt = self
             .builder
             .generate_inline_transformation_prompt(
                 user_prompt,
                 language_name,
+                file_path.as_deref(),
                 buffer,
                 range.start.0..range.end.0,
             )
@@ -695,6 +713,9 @@ impl CodegenAlternative {
                 .map(|language| language.name())
         };
 
+        let coalesce_window =
+            Duration::from_millis(AgentSettings::get_global(cx).inline_assistant_coalesce_ms);
+
         self.diff = Diff::default();
         self.status = CodegenStatus::Pending;
         let mut edit_start = self.range.start.to_offset(&snapshot);
@@ -849,7 +870,30 @@ impl CodegenAlternative {
                     }
                 });
 
-                while let Some((char_ops, line_ops)) = diff_rx.next().await {
+                while let Some((mut char_ops, mut line_ops)) = diff_rx.next().await {
+                    // Gather the diffs streamed during the coalescing window so that a fast model
+                    // produces one buffer edit per window rather than one per line. The window is
+                    // cut short when the stream ends. Line operations describe the whole diff so
+                    // far, so only the latest is kept.
+                    let mut flush = cx.background_executor().timer(coalesce_window).fuse();
+                    loop {
+                        let next_diff = if coalesce_window.is_zero() {
+                            match diff_rx.next().now_or_never() {
+                                Some(next_diff) => next_diff,
+                                None => break,
+                            }
+                        } else {
+                            futures::select_biased! {
+                                next_diff = diff_rx.next() => next_diff,
+                                _ = flush => break,
+                            }
+                        };
+                        let Some((next_char_ops, next_line_ops)) = next_diff else {
+                            break;
+                        };
+                        char_ops.extend(next_char_ops);
+                        line_ops = next_line_ops;
+                    }
                     codegen.update(cx, |codegen, cx| {
                         codegen.last_equal_ranges.clear();
 
@@ -1498,6 +1542,7 @@ impl Diff {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fs::FakeFs;
     use futures::{
         Stream,
         stream::{self},
@@ -1511,9 +1556,19 @@ mod tests {
         LanguageModelToolUse, StopReason, TokenUsage,
     };
     use languages::rust_lang;
+    use project::Project;
     use rand::prelude::*;
+    use serde_json::json;
     use settings::SettingsStore;
-    use std::{future, sync::Arc};
+    use std::{
+        future,
+        path::Path,
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering::SeqCst},
+        },
+    };
+    use util::path;
 
     #[gpui::test(iterations = 10)]
     async fn test_transform_autoindent(cx: &mut TestAppContext, mut rng: StdRng) {
@@ -1577,6 +1632,211 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_transform_coalesces_edits_streamed_within_window(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let text = indoc! {"
+            fn main() {
+                let x = 0;
+                for _ in 0..10 {
+                    x += 1;
+                }
+            }
+        "};
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(rust_lang(), cx));
+        let buffer = cx.new(|cx| MultiBuffer::singleton(buffer, cx));
+        let range = buffer.read_with(cx, |buffer, cx| {
+            let snapshot = buffer.snapshot(cx);
+            snapshot.anchor_before(Point::new(1, 0))..snapshot.anchor_after(Point::new(4, 5))
+        });
+        let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+        let codegen = cx.new(|cx| {
+            CodegenAlternative::new(
+                buffer.clone(),
+                range.clone(),
+                true,
+                prompt_builder,
+                Uuid::new_v4(),
+                cx,
+            )
+        });
+
+        let edit_count = Arc::new(AtomicUsize::new(0));
+        let _subscription = cx.update(|cx| {
+            let edit_count = edit_count.clone();
+            cx.subscribe(&buffer, move |_, event: &multi_buffer::Event, _| {
+                if let multi_buffer::Event::Edited { .. } = event {
+                    edit_count.fetch_add(1, SeqCst);
+                }
+            })
+        });
+
+        let chunks_tx = simulate_response_stream(&codegen, cx);
+
+        // Every chunk arrives within one coalescing window, so the diffs are applied together.
+        let new_text = concat!(
+            "       let mut x = 0;\n",
+            "       while x < 10 {\n",
+            "           x += 1;\n",
+            "       }",
+        );
+        let chunks = new_text.split_inclusive(' ').collect::<Vec<_>>();
+        for chunk in &chunks {
+            chunks_tx.unbounded_send(chunk.to_string()).unwrap();
+        }
+        drop(chunks_tx);
+        cx.background_executor.run_until_parked();
+
+        let edit_count = edit_count.load(SeqCst);
+        assert!(
+            edit_count < chunks.len(),
+            "expected streamed diffs to be coalesced, but {} chunks produced {} buffer edits",
+            chunks.len(),
+            edit_count
+        );
+
+        assert_eq!(
+            buffer.read_with(cx, |buffer, cx| buffer.snapshot(cx).text()),
+            indoc! {"
+                fn main() {
+                    let mut x = 0;
+                    while x < 10 {
+                        x += 1;
+                    }
+                }
+            "}
+        );
+    }
+
+    #[gpui::test]
+    async fn test_transform_with_buffer_reloaded_mid_stream(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let text = indoc! {"
+            fn main() {
+                let x = 0;
+                for _ in 0..10 {
+                    x += 1;
+                }
+            }
+        "};
+        let buffer = cx.new(|cx| Buffer::local(text, cx).with_language(rust_lang(), cx));
+        let multibuffer = cx.new(|cx| MultiBuffer::singleton(buffer.clone(), cx));
+        let range = multibuffer.read_with(cx, |buffer, cx| {
+            let snapshot = buffer.snapshot(cx);
+            snapshot.anchor_before(Point::new(1, 0))..snapshot.anchor_after(Point::new(4, 5))
+        });
+        let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+        let codegen = cx.new(|cx| {
+            CodegenAlternative::new(
+                multibuffer.clone(),
+                range.clone(),
+                true,
+                prompt_builder,
+                Uuid::new_v4(),
+                cx,
+            )
+        });
+
+        let chunks_tx = simulate_response_stream(&codegen, cx);
+        chunks_tx
+            .unbounded_send("       let mut x = 0;\n".to_string())
+            .unwrap();
+        cx.background_executor.run_until_parked();
+        // Let the coalescing window elapse so the first line is applied before the reload.
+        cx.background_executor
+            .advance_clock(Duration::from_millis(16));
+        cx.background_executor.run_until_parked();
+
+        // Reload the buffer the way `Buffer::reload` does, with a diff against new contents
+        // that another tool wrote above the range being generated.
+        let reloaded_text = buffer.read_with(cx, |buffer, _| {
+            format!("// Edited externally\n{}", buffer.text())
+        });
+        let diff = buffer
+            .update(cx, |buffer, cx| buffer.diff(reloaded_text, cx))
+            .await;
+        buffer.update(cx, |buffer, cx| buffer.apply_diff(diff, cx));
+
+        chunks_tx
+            .unbounded_send("       while x < 10 {\n".to_string())
+            .unwrap();
+        chunks_tx
+            .unbounded_send("           x += 1;\n       }".to_string())
+            .unwrap();
+        drop(chunks_tx);
+        cx.background_executor.run_until_parked();
+
+        assert_eq!(
+            buffer.read_with(cx, |buffer, _| buffer.text()),
+            indoc! {"
+                // Edited externally
+                fn main() {
+                    let mut x = 0;
+                    while x < 10 {
+                        x += 1;
+                    }
+                }
+            "}
+        );
+    }
+
+    #[gpui::test]
+    async fn test_prompt_file_path(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "src": { "main.rs": "fn main() {}\n" },
+                ".env": "SECRET=1\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs, [Path::new(path!("/project"))], cx).await;
+        let main_rs = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/src/main.rs"), cx)
+            })
+            .await
+            .unwrap();
+        let env = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/.env"), cx)
+            })
+            .await
+            .unwrap();
+        let prompt_file_path = |buffer: &Entity<Buffer>, cx: &mut TestAppContext| {
+            buffer.read_with(cx, |buffer, cx| {
+                CodegenAlternative::prompt_file_path(&buffer.snapshot(), cx)
+            })
+        };
+
+        assert_eq!(
+            prompt_file_path(&main_rs, cx).as_deref(),
+            Some(path!("src/main.rs"))
+        );
+        assert_eq!(
+            prompt_file_path(&env, cx),
+            None,
+            "files matching private_files are never named"
+        );
+
+        cx.update(|cx| {
+            SettingsStore::update_global(cx, |store, cx| {
+                store.update_user_settings(cx, |settings| {
+                    settings
+                        .agent
+                        .get_or_insert_default()
+                        .inline_assistant_include_file_path = Some(false);
+                });
+            });
+        });
+        assert_eq!(prompt_file_path(&main_rs, cx), None);
+    }
+
     #[gpui::test(iterations = 10)]
     async fn test_autoindent_when_generating_past_indentation(
         cx: &mut TestAppContext,
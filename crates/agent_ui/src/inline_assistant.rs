@@ -9,7 +9,7 @@ use uuid::Uuid;
 use crate::context::load_context;
 use crate::mention_set::MentionSet;
 use crate::{
-    AgentPanel,
+    AgentPanel, StopAllInlineAssists,
     buffer_codegen::{BufferCodegen, CodegenAlternative, CodegenEvent},
     inline_prompt_editor::{CodegenStatus, InlineAssistId, PromptEditor, PromptEditorEvent},
     terminal_inline_assistant::TerminalInlineAssistant,
@@ -65,7 +65,8 @@ pub fn init(fs: Arc<dyn Fs>, prompt_builder: Arc<PromptBuilder>, cx: &mut App) {
     })
     .detach();
 
-    cx.observe_new(|_workspace: &mut Workspace, window, cx| {
+    cx.observe_new(|workspace: &mut Workspace, window, cx| {
+        workspace.register_action(InlineAssistant::stop_all_inline_assists);
         let Some(window) = window else {
             return;
         };
@@ -79,6 +80,9 @@ pub fn init(fs: Arc<dyn Fs>, prompt_builder: Arc<PromptBuilder>, cx: &mut App) {
 
 const PROMPT_HISTORY_MAX_LEN: usize = 20;
 
+/// Identifies the toast explaining why inline assist was refused for a private file.
+struct PrivateFileInlineAssist;
+
 enum InlineAssistTarget {
     Editor(Entity<Editor>),
     Terminal(Entity<TerminalView>),
@@ -218,6 +222,20 @@ impl InlineAssistant {
             return;
         };
 
+        if let InlineAssistTarget::Editor(editor) = &inline_assist_target
+            && Self::selects_private_file(editor, window, cx)
+        {
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<PrivateFileInlineAssist>(),
+                    "Inline assist is unavailable for files matching the `private_files` setting",
+                )
+                .autohide(),
+                cx,
+            );
+            return;
+        }
+
         let configuration_error = |cx| {
             let model_registry = LanguageModelRegistry::read_global(cx);
             model_registry.configuration_error(model_registry.inline_assistant_model(), cx)
@@ -297,37 +315,29 @@ impl InlineAssistant {
         }
     }
 
-    fn codegen_ranges(
-        &mut self,
-        editor: &Entity<Editor>,
-        snapshot: &EditorSnapshot,
-        window: &mut Window,
-        cx: &mut App,
-    ) -> Option<(Vec<Range<Anchor>>, Selection<Point>)> {
-        let (initial_selections, newest_selection) = editor.update(cx, |editor, _| {
-            (
-                editor.selections.all::<Point>(&snapshot.display_snapshot),
-                editor
-                    .selections
-                    .newest::<Point>(&snapshot.display_snapshot),
-            )
+    /// Private files must never be sent to a model, matching how the agent's tools treat them.
+    /// Only the buffers the selections would create assists in are checked.
+    fn selects_private_file(editor: &Entity<Editor>, window: &mut Window, cx: &mut App) -> bool {
+        let snapshot = editor.update(cx, |editor, cx| editor.snapshot(window, cx));
+        let selections = editor.update(cx, |editor, _| {
+            editor.selections.all::<Point>(&snapshot.display_snapshot)
         });
+        let (selections, _) = Self::expand_selections(selections, &snapshot);
+        let buffer_snapshot = snapshot.buffer_snapshot();
+        selections
+            .iter()
+            .flat_map(|selection| {
+                buffer_snapshot.range_to_buffer_ranges(selection.start..selection.end)
+            })
+            .any(|(buffer, _, _)| buffer.file().is_some_and(|file| file.is_private()))
+    }
 
-        // Check if there is already an inline assistant that contains the
-        // newest selection, if there is, focus it
-        if let Some(editor_assists) = self.assists_by_editor.get(&editor.downgrade()) {
-            for assist_id in &editor_assists.assist_ids {
-                let assist = &self.assists[assist_id];
-                let range = assist.range.to_point(&snapshot.buffer_snapshot());
-                if range.start.row <= newest_selection.start.row
-                    && newest_selection.end.row <= range.end.row
-                {
-                    self.focus_assist(*assist_id, window, cx);
-                    return None;
-                }
-            }
-        }
-
+    /// Widens the selections to the lines an assist rewrites, merging the ones that overlap, and
+    /// returns them along with the newest one.
+    fn expand_selections(
+        initial_selections: Vec<Selection<Point>>,
+        snapshot: &EditorSnapshot,
+    ) -> (Vec<Selection<Point>>, Selection<Point>) {
         let mut selections = Vec::<Selection<Point>>::new();
         let mut newest_selection = None;
         for mut selection in initial_selections {
@@ -384,8 +394,42 @@ impl InlineAssistant {
             }
             selections.push(selection);
         }
+        (selections, newest_selection.unwrap())
+    }
+
+    fn codegen_ranges(
+        &mut self,
+        editor: &Entity<Editor>,
+        snapshot: &EditorSnapshot,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<(Vec<Range<Anchor>>, Selection<Point>)> {
+        let (initial_selections, newest_selection) = editor.update(cx, |editor, _| {
+            (
+                editor.selections.all::<Point>(&snapshot.display_snapshot),
+                editor
+                    .selections
+                    .newest::<Point>(&snapshot.display_snapshot),
+            )
+        });
+
+        // Check if there is already an inline assistant that contains the
+        // newest selection, if there is, focus it
+        if let Some(editor_assists) = self.assists_by_editor.get(&editor.downgrade()) {
+            for assist_id in &editor_assists.assist_ids {
+                let assist = &self.assists[assist_id];
+                let range = assist.range.to_point(&snapshot.buffer_snapshot());
+                if range.start.row <= newest_selection.start.row
+                    && newest_selection.end.row <= range.end.row
+                {
+                    self.focus_assist(*assist_id, window, cx);
+                    return None;
+                }
+            }
+        }
+
+        let (selections, newest_selection) = Self::expand_selections(initial_selections, snapshot);
         let snapshot = &snapshot.buffer_snapshot();
-        let newest_selection = newest_selection.unwrap();
 
         let mut codegen_ranges = Vec::new();
         for (buffer, buffer_range, _) in selections
@@ -1270,6 +1314,41 @@ impl InlineAssistant {
         assist.codegen.update(cx, |codegen, cx| codegen.stop(cx));
     }
 
+    fn stop_all_inline_assists(
+        workspace: &mut Workspace,
+        _: &StopAllInlineAssists,
+        _: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let stopped = Self::update_global(cx, |this, cx| this.stop_all_assists(cx))
+            + TerminalInlineAssistant::update_global(cx, |this, cx| this.stop_all_assists(cx));
+        let message = match stopped {
+            0 => "No inline assists were generating".to_string(),
+            1 => "Stopped 1 inline assist".to_string(),
+            count => format!("Stopped {count} inline assists"),
+        };
+        workspace.show_toast(
+            Toast::new(NotificationId::unique::<StopAllInlineAssists>(), message).autohide(),
+            cx,
+        );
+    }
+
+    /// Stops every editor assist that is still generating and returns how many were stopped.
+    pub fn stop_all_assists(&mut self, cx: &mut App) -> usize {
+        let pending_assist_ids = self
+            .assists
+            .iter()
+            .filter(|(_, assist)| {
+                matches!(assist.codegen.read(cx).status(cx), CodegenStatus::Pending)
+            })
+            .map(|(assist_id, _)| *assist_id)
+            .collect::<Vec<_>>();
+        for assist_id in &pending_assist_ids {
+            self.stop_assist(*assist_id, cx);
+        }
+        pending_assist_ids.len()
+    }
+
     fn update_editor_highlights(&self, editor: &Entity<Editor>, cx: &mut App) {
         let mut gutter_pending_ranges = Vec::new();
         let mut gutter_transformed_ranges = Vec::new();
@@ -1795,6 +1874,222 @@ fn merge_ranges(ranges: &mut Vec<Range<Anchor>>, buffer: &MultiBufferSnapshot) {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use editor::{
+        MultiBufferOffset,
+        actions::{MoveToBeginning, MoveToEnd},
+    };
+    use fs::FakeFs;
+    use gpui::{TestAppContext, VisualTestContext};
+    use multi_buffer::PathKey;
+    use serde_json::json;
+    use std::path::Path;
+    use util::path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            theme_settings::init(theme::LoadThemes::JustBase, cx);
+            editor::init(cx);
+            release_channel::init(semver::Version::new(0, 0, 0), cx);
+            LanguageModelRegistry::test(cx);
+            prompt_store::init(cx);
+        });
+    }
+
+    /// Opens `file_name` from a small project in a workspace and returns its editor.
+    async fn build_editor<'a>(
+        file_name: &str,
+        cx: &'a mut TestAppContext,
+    ) -> (Entity<Workspace>, Entity<Editor>, &'a mut VisualTestContext) {
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/project"),
+            json!({
+                "main.rs": "fn main() {\n    let x = 0;\n    println!(\"{x}\");\n}\n",
+                ".env": "SECRET=1\n",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [Path::new(path!("/project"))], cx).await;
+        let prompt_builder = Arc::new(PromptBuilder::new(None).unwrap());
+        cx.update(|cx| cx.set_global(InlineAssistant::new(fs, prompt_builder)));
+
+        let buffer = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(Path::new(path!("/project")).join(file_name), cx)
+            })
+            .await
+            .unwrap();
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let editor = cx.update(|window, cx| {
+            cx.new(|cx| Editor::for_buffer(buffer, Some(project), window, cx))
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+        });
+        (workspace, editor, cx)
+    }
+
+    fn select_ranges(
+        editor: &Entity<Editor>,
+        ranges: impl IntoIterator<Item = Range<usize>>,
+        cx: &mut VisualTestContext,
+    ) {
+        editor.update_in(cx, |editor, window, cx| {
+            editor.change_selections(Default::default(), window, cx, |selections| {
+                selections.select_ranges(
+                    ranges
+                        .into_iter()
+                        .map(|range| MultiBufferOffset(range.start)..MultiBufferOffset(range.end)),
+                )
+            })
+        });
+    }
+
+    fn assist(
+        workspace: &Entity<Workspace>,
+        editor: &Entity<Editor>,
+        prompt: &str,
+        cx: &mut VisualTestContext,
+    ) -> Option<InlineAssistId> {
+        cx.update(|window, cx| {
+            let project = workspace.read(cx).project().downgrade();
+            let thread_store = cx.new(|cx| ThreadStore::new(cx));
+            InlineAssistant::update_global(cx, |assistant, cx| {
+                assistant.assist(
+                    editor,
+                    workspace.downgrade(),
+                    project,
+                    thread_store,
+                    Some(prompt.to_string()),
+                    window,
+                    cx,
+                )
+            })
+        })
+    }
+
+    #[gpui::test]
+    async fn test_stop_all_assists_counts_pending_assists(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (workspace, editor, cx) = build_editor("main.rs", cx).await;
+
+        // Two cursors on separate lines produce two assists in one group.
+        select_ranges(&editor, [12..12, 42..42], cx);
+        let assist_id = assist(&workspace, &editor, "delete", cx).unwrap();
+
+        let stopped = cx.update(|window, cx| {
+            InlineAssistant::update_global(cx, |assistant, cx| {
+                assert_eq!(assistant.assists.len(), 2);
+                assistant.start_assist(assist_id, window, cx);
+                assistant.stop_all_assists(cx)
+            })
+        });
+        assert_eq!(stopped, 2);
+
+        let stopped = cx.update(|_, cx| {
+            InlineAssistant::update_global(cx, |assistant, cx| assistant.stop_all_assists(cx))
+        });
+        assert_eq!(stopped, 0, "stopped assists are no longer generating");
+    }
+
+    #[gpui::test]
+    async fn test_inline_assist_twice_focuses_the_existing_assist(cx: &mut TestAppContext) {
+        init_test(cx);
+        let (workspace, editor, cx) = build_editor("main.rs", cx).await;
+
+        select_ranges(&editor, [12..12], cx);
+        let assist_id = assist(&workspace, &editor, "delete", cx).unwrap();
+        cx.update(|window, cx| {
+            InlineAssistant::update_global(cx, |assistant, cx| {
+                assistant.start_assist(assist_id, window, cx)
+            })
+        });
+
+        // Invoking the assist again on the same line focuses the pending assist instead of
+        // starting another generation into the same range.
+        assert_eq!(assist(&workspace, &editor, "delete", cx), None);
+        cx.update(|_, cx| {
+            let assistant = cx.global::<InlineAssistant>();
+            assert_eq!(assistant.assists.len(), 1);
+            assert!(matches!(
+                assistant.assists[&assist_id].codegen.read(cx).status(cx),
+                CodegenStatus::Pending
+            ));
+        });
+    }
+
+    #[gpui::test]
+    async fn test_inline_assist_is_refused_when_a_private_file_is_selected(
+        cx: &mut TestAppContext,
+    ) {
+        init_test(cx);
+        let (workspace, editor, cx) = build_editor("main.rs", cx).await;
+
+        let project = workspace.read_with(cx, |workspace, _| workspace.project().clone());
+        let main_rs = editor.read_with(cx, |editor, cx| {
+            editor.buffer().read(cx).as_singleton().unwrap()
+        });
+        let env = project
+            .update(cx, |project, cx| {
+                project.open_local_buffer(path!("/project/.env"), cx)
+            })
+            .await
+            .unwrap();
+        let multibuffer = cx.new(|cx| {
+            let mut multibuffer = MultiBuffer::new(language::Capability::ReadWrite);
+            multibuffer.set_excerpts_for_path(
+                PathKey::sorted(0),
+                main_rs,
+                [Point::new(0, 0)..Point::new(3, 1)],
+                0,
+                cx,
+            );
+            multibuffer.set_excerpts_for_path(
+                PathKey::sorted(1),
+                env,
+                [Point::new(0, 0)..Point::new(0, 8)],
+                0,
+                cx,
+            );
+            multibuffer
+        });
+        let editor = cx.update(|window, cx| {
+            cx.new(|cx| Editor::for_multibuffer(multibuffer, Some(project), window, cx))
+        });
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.add_item_to_active_pane(Box::new(editor.clone()), None, true, window, cx);
+        });
+        let refused = |cx: &mut VisualTestContext| {
+            workspace.update_in(cx, |workspace, window, cx| {
+                InlineAssistant::inline_assist(workspace, &Default::default(), window, cx);
+                workspace
+                    .notification_ids()
+                    .contains(&NotificationId::unique::<PrivateFileInlineAssist>())
+            })
+        };
+
+        // The private file being open elsewhere in the multibuffer doesn't matter...
+        editor.update_in(cx, |editor, window, cx| {
+            editor.move_to_beginning(&MoveToBeginning, window, cx)
+        });
+        assert!(!refused(cx));
+
+        // ...but selecting it refuses the assist.
+        editor.update_in(cx, |editor, window, cx| {
+            editor.move_to_end(&MoveToEnd, window, cx)
+        });
+        assert!(refused(cx), ".env matches the default private_files");
+        cx.update(|_, cx| assert!(cx.global::<InlineAssistant>().assists.is_empty()));
+    }
+}
+
 #[cfg(all(test, feature = "unit-eval"))]
 pub mod evals {
     use crate::InlineAssistant;
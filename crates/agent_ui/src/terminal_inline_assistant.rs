@@ -209,6 +209,20 @@ impl TerminalInlineAssistant {
         assist.codegen.update(cx, |codegen, cx| codegen.stop(cx));
     }
 
+    /// Stops every terminal assist that is still generating and returns how many were stopped.
+    pub fn stop_all_assists(&mut self, cx: &mut App) -> usize {
+        let pending_assist_ids = self
+            .assists
+            .iter()
+            .filter(|(_, assist)| matches!(assist.codegen.read(cx).status, CodegenStatus::Pending))
+            .map(|(assist_id, _)| *assist_id)
+            .collect::<Vec<_>>();
+        for assist_id in &pending_assist_ids {
+            self.stop_assist(*assist_id, cx);
+        }
+        pending_assist_ids.len()
+    }
+
     fn request_for_inline_assist(
         &self,
         assist_id: TerminalInlineAssistId,
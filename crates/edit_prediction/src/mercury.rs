@@ -152,6 +152,9 @@ impl Mercury {
                 prompt_cache_key: None,
                 reasoning_effort: None,
                 service_tier: None,
+                user: None,
+                seed: None,
+                logit_bias: None,
             };
 
             let buf = serde_json::to_vec(&request_body)?;
@@ -51,6 +51,9 @@ impl PlainOpenAiClient {
             tools: Vec::new(),
             prompt_cache_key: None,
             reasoning_effort: None,
+            user: None,
+            seed: None,
+            logit_bias: None,
         };
 
         let response = non_streaming_completion(
@@ -513,6 +516,9 @@ impl BatchingOpenAiClient {
                     tools: Vec::new(),
                     prompt_cache_key: None,
                     reasoning_effort: None,
+                    user: None,
+                    seed: None,
+                    logit_bias: None,
                 };
 
                 let custom_id = format!("req_hash_{}", hash);
@@ -744,6 +744,8 @@ actions!(
         Redo,
         /// Redoes the last selection change.
         RedoSelection,
+        /// Restarts clangd so that it reloads the compilation database.
+        ReloadCompilationDatabase,
         /// Renames the symbol at cursor.
         Rename,
         /// Restarts the language server for the current file.
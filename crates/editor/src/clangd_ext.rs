@@ -1,22 +1,49 @@
 use anyhow::Context as _;
+use collections::HashSet;
+use futures::{FutureExt as _, channel::oneshot};
 use gpui::{App, Context, Entity, TaskExt, Window};
 use language::Language;
+use lsp::LanguageServerSelector;
 use project::lsp_store::lsp_ext_command::SwitchSourceHeaderResult;
 use rpc::proto;
+use std::{path::PathBuf, time::Duration};
 use url::Url;
 use util::paths::{PathStyle, UrlExt as _};
-use workspace::{OpenOptions, OpenVisible};
+use workspace::{OpenOptions, OpenVisible, Toast, notifications::NotificationId};
 
 use crate::lsp_ext::find_specific_language_server_in_selection;
 
-use crate::{Editor, SwitchSourceHeader, element::register_action};
+use crate::{Editor, ReloadCompilationDatabase, SwitchSourceHeader, element::register_action};
 
 use project::lsp_store::clangd_ext::CLANGD_SERVER_NAME;
 
+/// Some clangd versions answer `textDocument/switchSourceHeader` with an empty result until the
+/// file is indexed, so an empty response is retried after each of these delays before giving up.
+const EMPTY_SWITCH_SOURCE_HEADER_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(100),
+    Duration::from_millis(300),
+    Duration::from_millis(900),
+];
+
 fn is_c_language(language: &Language) -> bool {
     language.name() == "C++" || language.name() == "C"
 }
 
+/// Converts clangd's source/header answer into a local path. Some clangd wrappers answer with
+/// their own URI schemes for generated headers, which don't name a file on disk.
+fn counterpart_path(uri: &str, path_style: PathStyle) -> anyhow::Result<PathBuf> {
+    let url = Url::parse(uri).with_context(|| {
+        format!("Parsing URL \"{uri}\" returned from switch source/header failed")
+    })?;
+    anyhow::ensure!(
+        url.scheme() == "file",
+        "Cannot open the source/header counterpart: {CLANGD_SERVER_NAME} returned a \"{}\" URI, which is not a file",
+        url.scheme()
+    );
+    url.to_file_path_ext(path_style)
+        .map_err(|()| anyhow::anyhow!("URL conversion to file path failed for \"{uri}\""))
+}
+
 pub fn switch_source_header(
     editor: &mut Editor,
     _: &SwitchSourceHeader,
@@ -45,50 +72,76 @@ pub fn switch_source_header(
                 .map(|path| path.display(PathStyle::local()).to_string())
                 .unwrap_or_else(|| "Unknown".to_string())
         });
+        log::debug!(
+            "Switching source/header for \"{source_file}\" using {CLANGD_SERVER_NAME} (server id {server_to_query})"
+        );
 
-        let switch_source_header = if let Some((client, project_id)) = upstream_client {
-            let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
-            let request = proto::LspExtSwitchSourceHeader {
-                project_id,
-                buffer_id: buffer_id.to_proto(),
+        let mut retry_delays = EMPTY_SWITCH_SOURCE_HEADER_RETRY_DELAYS.iter();
+        let switch_source_header = loop {
+            let result = if let Some((client, project_id)) = &upstream_client {
+                let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+                let request = proto::LspExtSwitchSourceHeader {
+                    project_id: *project_id,
+                    buffer_id: buffer_id.to_proto(),
+                };
+                let response = client
+                    .request(request)
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Switch source/header proto request to {CLANGD_SERVER_NAME} (server id {server_to_query}) for path \"{source_file}\" failed"
+                        )
+                    })?;
+                SwitchSourceHeaderResult(response.target_file)
+            } else {
+                project
+                    .update(cx, |project, cx| {
+                        project.request_lsp(
+                            buffer.clone(),
+                            project::LanguageServerToQuery::Other(server_to_query),
+                            project::lsp_store::lsp_ext_command::SwitchSourceHeader,
+                            cx,
+                        )
+                    })
+                    .await
+                    .with_context(|| {
+                        format!(
+                            "Switch source/header LSP request to {CLANGD_SERVER_NAME} (server id {server_to_query}) for path \"{source_file}\" failed"
+                        )
+                    })?
             };
-            let response = client
-                .request(request)
-                .await
-                .context("lsp ext switch source header proto request")?;
-            SwitchSourceHeaderResult(response.target_file)
-        } else {
-            project
-                .update(cx, |project, cx| {
-                    project.request_lsp(
-                        buffer,
-                        project::LanguageServerToQuery::Other(server_to_query),
-                        project::lsp_store::lsp_ext_command::SwitchSourceHeader,
-                        cx,
-                    )
-                })
-                .await
-                .with_context(|| {
-                    format!("Switch source/header LSP request for path \"{source_file}\" failed")
-                })?
+            match retry_delays.next() {
+                Some(delay) if result.0.is_empty() => {
+                    log::debug!(
+                        "clangd found no source/header counterpart for \"{source_file}\", retrying in {delay:?}"
+                    );
+                    cx.background_executor().timer(*delay).await;
+                }
+                _ => break result,
+            }
         };
 
         if switch_source_header.0.is_empty() {
             return Ok(());
         }
         let path_style = workspace.update(cx, |ws, cx| ws.path_style(cx));
-        let path = Url::parse(&switch_source_header.0).with_context(|| {
-            format!(
-                "Parsing URL \"{}\" returned from switch source/header failed",
-                switch_source_header.0
-            )
-        })?;
-        let path = path.to_file_path_ext(path_style).map_err(|()| {
-            anyhow::anyhow!(
-                "URL conversion to file path failed for \"{}\"",
-                switch_source_header.0
-            )
-        })?;
+        let path = match counterpart_path(&switch_source_header.0, path_style) {
+            Ok(path) => path,
+            Err(error) => {
+                workspace.update(cx, |workspace, cx| {
+                    struct SwitchSourceHeaderToast;
+
+                    workspace.show_toast(
+                        Toast::new(
+                            NotificationId::unique::<SwitchSourceHeaderToast>(),
+                            error.to_string(),
+                        ),
+                        cx,
+                    )
+                });
+                return Err(error);
+            }
+        };
 
         workspace
             .update_in(cx, |workspace, window, cx| {
@@ -114,7 +167,97 @@ pub fn switch_source_header(
     .detach_and_log_err(cx);
 }
 
+/// How long to wait for clangd to come back up before giving up on reporting the reload.
+const RELOAD_COMPILATION_DATABASE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// clangd does not always notice a regenerated `compile_commands.json`, so restarting it
+/// is the reliable way to pick up the new compilation database.
+pub fn reload_compilation_database(
+    editor: &mut Editor,
+    _: &ReloadCompilationDatabase,
+    window: &mut Window,
+    cx: &mut Context<Editor>,
+) {
+    let Some(project) = editor.project.clone() else {
+        return;
+    };
+    let Some(workspace) = editor.workspace() else {
+        return;
+    };
+    let Some((_, _, server_id, buffer)) =
+        find_specific_language_server_in_selection(editor, cx, is_c_language, CLANGD_SERVER_NAME)
+    else {
+        return;
+    };
+
+    let (restarted_tx, restarted_rx) = oneshot::channel();
+    let mut restarted_tx = Some(restarted_tx);
+    let subscription = cx.subscribe(&project, move |_, _, event: &project::Event, _| {
+        if let project::Event::LanguageServerAdded(_, name, _) = event
+            && *name == CLANGD_SERVER_NAME
+            && let Some(restarted_tx) = restarted_tx.take()
+        {
+            restarted_tx.send(()).ok();
+        }
+    });
+    project.update(cx, |project, cx| {
+        project.restart_language_servers_for_buffers(
+            vec![buffer],
+            HashSet::from_iter([LanguageServerSelector::Id(server_id)]),
+            false,
+            cx,
+        );
+    });
+
+    cx.spawn_in(window, async move |_, cx| {
+        let mut timeout = cx
+            .background_executor()
+            .timer(RELOAD_COMPILATION_DATABASE_TIMEOUT)
+            .fuse();
+        let restarted = futures::select_biased! {
+            restarted = restarted_rx.fuse() => restarted.is_ok(),
+            () = timeout => false,
+        };
+        drop(subscription);
+        if !restarted {
+            log::warn!("timed out waiting for {CLANGD_SERVER_NAME} to restart");
+            return;
+        }
+
+        workspace.update(cx, |workspace, cx| {
+            struct ReloadCompilationDatabaseToast;
+
+            workspace.show_toast(
+                Toast::new(
+                    NotificationId::unique::<ReloadCompilationDatabaseToast>(),
+                    "Restarted clangd to reload the compilation database",
+                )
+                .autohide(),
+                cx,
+            )
+        });
+    })
+    .detach();
+}
+
 pub fn apply_related_actions(editor: &Entity<Editor>, window: &mut Window, cx: &mut App) {
+    if let Some(project) = editor.read(cx).project()
+        && editor
+            .read(cx)
+            .buffer()
+            .read(cx)
+            .all_buffers()
+            .into_iter()
+            .any(|buffer| {
+                project
+                    .read(cx)
+                    .language_server_id_for_name(buffer.read(cx), &CLANGD_SERVER_NAME, cx)
+                    .is_some()
+            })
+    {
+        register_action(editor, window, reload_compilation_database);
+    }
+
     if editor
         .read(cx)
         .buffer()
@@ -127,3 +270,24 @@ pub fn apply_related_actions(editor: &Entity<Editor>, window: &mut Window, cx: &
         register_action(editor, window, switch_source_header);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counterpart_path_for_file_uri() {
+        assert_eq!(
+            counterpart_path("file:///home/user/project/foo.h", PathStyle::Unix).unwrap(),
+            PathBuf::from("/home/user/project/foo.h")
+        );
+    }
+
+    #[test]
+    fn test_counterpart_path_rejects_other_schemes() {
+        let error = counterpart_path("generated:///build/gen/foo.pb.h", PathStyle::Unix)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("\"generated\""), "unexpected error: {error}");
+    }
+}
@@ -171,7 +171,7 @@ impl ApiKeyState {
         }
 
         if let Some(key) = &self.env_var.value
-            && !key.is_empty()
+            && !key.trim().is_empty()
         {
             let api_key = ApiKey::from_env(self.env_var.name.clone(), key);
             self.url = url;
@@ -230,10 +230,12 @@ impl ApiKey {
         &self.key
     }
 
+    /// Keys from the environment are often exported from a file (e.g. `$(cat key.txt)`),
+    /// so surrounding whitespace such as a trailing newline is stripped.
     pub fn from_env(env_var_name: SharedString, key: &str) -> Self {
         Self {
             source: ApiKeySource::EnvVar(env_var_name),
-            key: key.into(),
+            key: key.trim().into(),
         }
     }
 
@@ -296,3 +298,73 @@ impl Display for ApiKeySource {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::{AppContext as _, TestAppContext};
+    use std::{future::Future, pin::Pin};
+
+    const URL: &str = "https://api.openai.com/v1";
+
+    struct FakeCredentialsProvider;
+
+    impl CredentialsProvider for FakeCredentialsProvider {
+        fn read_credentials<'a>(
+            &'a self,
+            _url: &'a str,
+            _cx: &'a AsyncApp,
+        ) -> Pin<Box<dyn Future<Output = Result<Option<(String, Vec<u8>)>>> + 'a>> {
+            Box::pin(async { Ok(Some(("Bearer".to_string(), b"sk-keychain".to_vec()))) })
+        }
+
+        fn write_credentials<'a>(
+            &'a self,
+            _url: &'a str,
+            _username: &'a str,
+            _password: &'a [u8],
+            _cx: &'a AsyncApp,
+        ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn delete_credentials<'a>(
+            &'a self,
+            _url: &'a str,
+            _cx: &'a AsyncApp,
+        ) -> Pin<Box<dyn Future<Output = Result<()>> + 'a>> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    fn get_this(state: &mut ApiKeyState) -> &mut ApiKeyState {
+        state
+    }
+
+    #[test]
+    fn test_key_from_env_is_trimmed() {
+        let api_key = ApiKey::from_env("OPENAI_API_KEY".into(), "  sk\n");
+        assert_eq!(api_key.key(), "sk");
+    }
+
+    #[gpui::test]
+    async fn test_whitespace_only_env_var_falls_back_to_keychain(cx: &mut TestAppContext) {
+        let env_var = EnvVar {
+            name: "OPENAI_API_KEY".into(),
+            value: Some(" \n".to_string()),
+        };
+        let state = cx.new(|_| ApiKeyState::new(URL.into(), env_var));
+
+        state
+            .update(cx, |state, cx| {
+                state.load_if_needed(URL.into(), get_this, Arc::new(FakeCredentialsProvider), cx)
+            })
+            .await
+            .unwrap();
+
+        state.read_with(cx, |state, _| {
+            assert!(!state.is_from_env_var());
+            assert_eq!(state.key(URL).as_deref(), Some("sk-keychain"));
+        });
+    }
+}
@@ -26,8 +26,8 @@ pub use crate::request::*;
 pub use crate::role::*;
 pub use crate::tool_schema::LanguageModelToolSchemaFormat;
 pub use crate::util::{
-    fix_streamed_json, is_context_window_exceeded_message, parse_prompt_too_long,
-    parse_tool_arguments,
+    fix_streamed_json, is_context_window_exceeded_message, parse_model_not_found,
+    parse_prompt_too_long, parse_tool_arguments,
 };
 pub use gpui_shared_string::SharedString;
 
@@ -146,6 +146,14 @@ pub enum LanguageModelCompletionError {
     },
     #[error("language model provider API endpoint not found")]
     ApiEndpointNotFound { provider: LanguageModelProviderName },
+    #[error(
+        "{provider}'s API could not find the model: {message} \
+        Check that your API key has access to it, or choose another model."
+    )]
+    ModelNotFound {
+        provider: LanguageModelProviderName,
+        message: String,
+    },
     #[error("I/O error reading response from {provider}'s API")]
     ApiReadResponseError {
         provider: LanguageModelProviderName,
@@ -253,7 +261,10 @@ impl LanguageModelCompletionError {
             }
             StatusCode::UNAUTHORIZED => Self::AuthenticationError { provider, message },
             StatusCode::FORBIDDEN => Self::PermissionError { provider, message },
-            StatusCode::NOT_FOUND => Self::ApiEndpointNotFound { provider },
+            StatusCode::NOT_FOUND => match parse_model_not_found(&message) {
+                Some(message) => Self::ModelNotFound { provider, message },
+                None => Self::ApiEndpointNotFound { provider },
+            },
             StatusCode::PAYLOAD_TOO_LARGE => Self::PromptTooLarge {
                 tokens: parse_prompt_too_long(&message),
             },
@@ -683,6 +694,42 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_from_http_status_maps_model_not_found() {
+        let error = LanguageModelCompletionError::from_http_status(
+            String::from("OpenAI").into(),
+            StatusCode::NOT_FOUND,
+            r#"{"error":{"message":"The model `gpt-9` does not exist or you do not have access to it.","type":"invalid_request_error","param":null,"code":"model_not_found"}}"#.to_string(),
+            None,
+        );
+
+        match error {
+            LanguageModelCompletionError::ModelNotFound { provider, message } => {
+                assert_eq!(provider.0, "OpenAI");
+                assert_eq!(
+                    message,
+                    "The model `gpt-9` does not exist or you do not have access to it."
+                );
+            }
+            _ => panic!(
+                "Expected ModelNotFound for model_not_found, got: {:?}",
+                error
+            ),
+        }
+
+        let error = LanguageModelCompletionError::from_http_status(
+            String::from("OpenAI").into(),
+            StatusCode::NOT_FOUND,
+            "Not Found".to_string(),
+            None,
+        );
+
+        assert!(matches!(
+            error,
+            LanguageModelCompletionError::ApiEndpointNotFound { .. }
+        ));
+    }
+
     #[test]
     fn test_from_cloud_failure_with_standard_format() {
         let error = LanguageModelCompletionError::from_cloud_failure(
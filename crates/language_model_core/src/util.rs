@@ -55,6 +55,18 @@ pub fn is_context_window_exceeded_message(message: &str) -> bool {
     message.contains("context_length_exceeded") || message.contains("exceeds the context window")
 }
 
+/// Recognizes OpenAI-style errors for a model that doesn't exist or that the API key can't
+/// access, and returns the API's description of the problem, which names the model.
+pub fn parse_model_not_found(message: &str) -> Option<String> {
+    if !message.contains("model_not_found") {
+        return None;
+    }
+    let description = serde_json::from_str::<serde_json::Value>(message)
+        .ok()
+        .and_then(|error| Some(error.pointer("/error/message")?.as_str()?.to_string()));
+    Some(description.unwrap_or_else(|| message.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
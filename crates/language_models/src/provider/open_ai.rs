@@ -1,5 +1,5 @@
 use anyhow::Result;
-use collections::BTreeMap;
+use collections::{BTreeMap, HashMap};
 use credentials_provider::CredentialsProvider;
 use futures::{FutureExt, StreamExt, future::BoxFuture};
 use gpui::{App, AppContext, AsyncApp, Context, Entity, SharedString, Task};
@@ -38,6 +38,9 @@ pub struct OpenAiSettings {
     pub api_url: String,
     pub available_models: Vec<AvailableModel>,
     pub custom_headers: CustomHeaders,
+    pub user: Option<String>,
+    pub seed: Option<u64>,
+    pub logit_bias: Option<HashMap<String, f32>>,
 }
 
 pub struct OpenAiLanguageModelProvider {
@@ -356,19 +359,28 @@ pub struct OpenAiLanguageModel {
 impl OpenAiLanguageModel {
     fn stream_completion(
         &self,
-        request: open_ai::Request,
+        mut request: open_ai::Request,
         cx: &AsyncApp,
     ) -> BoxFuture<'static, Result<futures::stream::BoxStream<'static, Result<ResponseStreamEvent>>>>
     {
         let http_client = self.http_client.clone();
 
-        let (api_key, api_url, extra_headers) = self.state.read_with(cx, |state, cx| {
-            let api_url = OpenAiLanguageModelProvider::api_url(cx);
-            let extra_headers = OpenAiLanguageModelProvider::settings(cx)
-                .custom_headers
-                .clone();
-            (state.api_key_state.key(&api_url), api_url, extra_headers)
-        });
+        let (api_key, api_url, extra_headers, user, seed, logit_bias) =
+            self.state.read_with(cx, |state, cx| {
+                let api_url = OpenAiLanguageModelProvider::api_url(cx);
+                let settings = OpenAiLanguageModelProvider::settings(cx);
+                (
+                    state.api_key_state.key(&api_url),
+                    api_url,
+                    settings.custom_headers.clone(),
+                    settings.user.clone(),
+                    settings.seed,
+                    settings.logit_bias.clone(),
+                )
+            });
+        request.user = user;
+        request.seed = seed;
+        request.logit_bias = logit_bias;
 
         let future = self.request_limiter.stream(async move {
             let provider = PROVIDER_NAME;
@@ -392,19 +404,23 @@ impl OpenAiLanguageModel {
 
     fn stream_response(
         &self,
-        request: ResponseRequest,
+        mut request: ResponseRequest,
         cx: &AsyncApp,
     ) -> BoxFuture<'static, Result<futures::stream::BoxStream<'static, Result<ResponsesStreamEvent>>>>
     {
         let http_client = self.http_client.clone();
 
-        let (api_key, api_url, extra_headers) = self.state.read_with(cx, |state, cx| {
+        let (api_key, api_url, extra_headers, user) = self.state.read_with(cx, |state, cx| {
             let api_url = OpenAiLanguageModelProvider::api_url(cx);
-            let extra_headers = OpenAiLanguageModelProvider::settings(cx)
-                .custom_headers
-                .clone();
-            (state.api_key_state.key(&api_url), api_url, extra_headers)
+            let settings = OpenAiLanguageModelProvider::settings(cx);
+            (
+                state.api_key_state.key(&api_url),
+                api_url,
+                settings.custom_headers.clone(),
+                settings.user.clone(),
+            )
         });
+        request.user = user;
 
         let provider = PROVIDER_NAME;
         let future = self.request_limiter.stream(async move {
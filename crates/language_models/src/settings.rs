@@ -44,6 +44,25 @@ fn custom_headers_from(
         .unwrap_or_default()
 }
 
+fn logit_bias_from(
+    provider_name: &str,
+    raw: Option<HashMap<String, f32>>,
+) -> Option<HashMap<String, f32>> {
+    let logit_bias = raw?
+        .into_iter()
+        .filter(|(token, bias)| {
+            let in_range = (-100.0..=100.0).contains(bias);
+            if !in_range {
+                log::warn!(
+                    "ignoring {provider_name} logit bias for token `{token}`: {bias} is outside -100..=100"
+                );
+            }
+            in_range
+        })
+        .collect::<HashMap<_, _>>();
+    (!logit_bias.is_empty()).then_some(logit_bias)
+}
+
 impl settings::Settings for AllLanguageModelSettings {
     const PRESERVED_KEYS: Option<&'static [&'static str]> = Some(&["version"]);
 
@@ -173,6 +192,9 @@ impl settings::Settings for AllLanguageModelSettings {
                 api_url: openai.api_url.unwrap(),
                 available_models: openai.available_models.unwrap_or_default(),
                 custom_headers: custom_headers_from("OpenAI", openai.custom_headers, &[]),
+                user: openai.user.filter(|user| !user.trim().is_empty()),
+                seed: openai.seed,
+                logit_bias: logit_bias_from("OpenAI", openai.logit_bias),
             },
             openai_compatible: openai_compatible
                 .into_iter()
@@ -224,6 +224,9 @@ pub fn into_open_ai(
         }),
         reasoning_effort,
         service_tier,
+        user: None,
+        seed: None,
+        logit_bias: None,
     })
 }
 
@@ -358,6 +361,7 @@ pub fn into_open_ai_response(
         service_tier,
         context_management: compact_at_tokens
             .map(|compact_threshold| vec![ContextManagement::Compaction { compact_threshold }]),
+        user: None,
     }
 }
 
@@ -678,15 +682,25 @@ fn add_message_content_part(
 
 pub struct OpenAiEventMapper {
     tool_calls_by_index: HashMap<usize, RawToolCall>,
+    message_id: Option<String>,
+    system_fingerprint: Option<String>,
 }
 
 impl OpenAiEventMapper {
     pub fn new() -> Self {
         Self {
             tool_calls_by_index: HashMap::default(),
+            message_id: None,
+            system_fingerprint: None,
         }
     }
 
+    /// The backend configuration that served the completion, which together with a `seed`
+    /// tells whether two completions are expected to be reproducible.
+    pub fn system_fingerprint(&self) -> Option<&str> {
+        self.system_fingerprint.as_deref()
+    }
+
     pub fn map_stream(
         mut self,
         events: Pin<Box<dyn Send + Stream<Item = Result<ResponseStreamEvent>>>>,
@@ -705,6 +719,22 @@ impl OpenAiEventMapper {
         event: ResponseStreamEvent,
     ) -> Vec<Result<LanguageModelCompletionEvent, LanguageModelCompletionError>> {
         let mut events = Vec::new();
+        if let Some(system_fingerprint) = event.system_fingerprint.filter(|fingerprint| {
+            !fingerprint.is_empty() && self.system_fingerprint.as_ref() != Some(fingerprint)
+        }) {
+            log::info!("OpenAI completion served with system fingerprint {system_fingerprint}");
+            self.system_fingerprint = Some(system_fingerprint);
+        }
+
+        if self.message_id.is_none()
+            && let Some(id) = event.id.filter(|id| !id.is_empty())
+        {
+            self.message_id = Some(id.clone());
+            events.push(Ok(LanguageModelCompletionEvent::StartMessage {
+                message_id: id,
+            }));
+        }
+
         if let Some(usage) = event.usage
             && let Some(prompt_tokens) = usage.prompt_tokens
             && let Some(completion_tokens) = usage.completion_tokens
@@ -721,7 +751,7 @@ impl OpenAiEventMapper {
             return events;
         };
 
-        if let Some(delta) = choice.delta.as_ref() {
+        if let Some(delta) = choice.delta.as_ref().or(choice.message.as_ref()) {
             if let Some(reasoning) = delta.reasoning.clone() {
                 push_thinking_event(reasoning, &mut events);
             }
@@ -1486,7 +1516,7 @@ mod tests {
 
     use super::*;
     use crate::{
-        ChoiceDelta, FunctionChunk, ResponseMessageDelta, ResponseStreamEvent, ToolCallChunk,
+        ChoiceDelta, FunctionChunk, ResponseMessageDelta, ResponseStreamEvent, ToolCallChunk, Usage,
     };
 
     fn map_response_events(events: Vec<ResponsesStreamEvent>) -> Vec<LanguageModelCompletionEvent> {
@@ -3840,6 +3870,7 @@ mod tests {
     #[test]
     fn stream_maps_reasoning() {
         let events = map_completion_events(vec![ResponseStreamEvent {
+            id: None,
             choices: vec![ChoiceDelta {
                 index: 0,
                 delta: Some(ResponseMessageDelta {
@@ -3849,9 +3880,11 @@ mod tests {
                     tool_calls: None,
                     reasoning_content: None,
                 }),
+                message: None,
                 finish_reason: None,
             }],
             usage: None,
+            system_fingerprint: None,
         }]);
 
         assert_eq!(
@@ -3863,6 +3896,149 @@ mod tests {
         );
     }
 
+    #[test]
+    fn stream_prefers_delta_over_full_message() {
+        let message = |content: &str| ResponseMessageDelta {
+            role: None,
+            content: Some(content.into()),
+            reasoning: None,
+            tool_calls: None,
+            reasoning_content: None,
+        };
+        let events = map_completion_events(vec![
+            ResponseStreamEvent {
+                id: None,
+                choices: vec![ChoiceDelta {
+                    index: 0,
+                    delta: Some(message("Hel")),
+                    message: Some(message("Hello")),
+                    finish_reason: None,
+                }],
+                usage: None,
+                system_fingerprint: None,
+            },
+            ResponseStreamEvent {
+                id: None,
+                choices: vec![ChoiceDelta {
+                    index: 0,
+                    delta: None,
+                    message: Some(message("lo")),
+                    finish_reason: None,
+                }],
+                usage: None,
+                system_fingerprint: None,
+            },
+        ]);
+
+        assert_eq!(
+            events,
+            vec![
+                LanguageModelCompletionEvent::Text("Hel".into()),
+                LanguageModelCompletionEvent::Text("lo".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_records_system_fingerprint_without_id() {
+        let mut mapper = OpenAiEventMapper::new();
+        let events = mapper.map_event(ResponseStreamEvent {
+            id: None,
+            choices: Vec::new(),
+            usage: None,
+            system_fingerprint: Some("fp_44709d6fcb".into()),
+        });
+
+        assert!(events.is_empty());
+        assert_eq!(mapper.system_fingerprint(), Some("fp_44709d6fcb"));
+    }
+
+    #[test]
+    fn stream_maps_trailing_usage_only_event() {
+        let mut mapper = OpenAiEventMapper::new();
+        let events = [
+            ResponseStreamEvent {
+                id: None,
+                choices: vec![ChoiceDelta {
+                    index: 0,
+                    delta: Some(ResponseMessageDelta {
+                        role: None,
+                        content: Some("Hello".into()),
+                        reasoning: None,
+                        tool_calls: None,
+                        reasoning_content: None,
+                    }),
+                    message: None,
+                    finish_reason: Some("stop".into()),
+                }],
+                usage: None,
+                system_fingerprint: None,
+            },
+            ResponseStreamEvent {
+                id: None,
+                choices: Vec::new(),
+                usage: Some(Usage {
+                    prompt_tokens: Some(12),
+                    completion_tokens: Some(3),
+                    total_tokens: Some(15),
+                }),
+                system_fingerprint: None,
+            },
+        ]
+        .into_iter()
+        .flat_map(|event| mapper.map_event(event))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("a usage-only event should not be an error");
+
+        assert_eq!(
+            events,
+            vec![
+                LanguageModelCompletionEvent::Text("Hello".into()),
+                LanguageModelCompletionEvent::Stop(StopReason::EndTurn),
+                LanguageModelCompletionEvent::UsageUpdate(TokenUsage {
+                    input_tokens: 12,
+                    output_tokens: 3,
+                    cache_creation_input_tokens: 0,
+                    cache_read_input_tokens: 0,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_emits_start_message_for_first_completion_id() {
+        let text_event = |text: &str| ResponseStreamEvent {
+            id: Some("chatcmpl-123".into()),
+            choices: vec![ChoiceDelta {
+                index: 0,
+                delta: Some(ResponseMessageDelta {
+                    role: None,
+                    content: Some(text.into()),
+                    reasoning: None,
+                    tool_calls: None,
+                    reasoning_content: None,
+                }),
+                message: None,
+                finish_reason: None,
+            }],
+            usage: None,
+            system_fingerprint: None,
+        };
+
+        let events = map_completion_events(vec![text_event("Hello"), text_event(" world")]);
+
+        assert_eq!(
+            events,
+            vec![
+                LanguageModelCompletionEvent::StartMessage {
+                    message_id: "chatcmpl-123".into(),
+                },
+                LanguageModelCompletionEvent::Text("Hello".into()),
+                LanguageModelCompletionEvent::Text(" world".into()),
+            ]
+        );
+    }
+
     #[test]
     fn stream_maps_preserves_tool_id_and_name_across_empty_deltas() {
         // DashScope sends id="" and name="" in subsequent tool_calls delta
@@ -3872,6 +4048,7 @@ mod tests {
         let events = vec![
             // First chunk: id and name are present
             ResponseStreamEvent {
+                id: None,
                 choices: vec![ChoiceDelta {
                     index: 0,
                     delta: Some(ResponseMessageDelta {
@@ -3888,12 +4065,15 @@ mod tests {
                         }]),
                         reasoning_content: None,
                     }),
+                    message: None,
                     finish_reason: None,
                 }],
                 usage: None,
+                system_fingerprint: None,
             },
             // Subsequent chunks: DashScope sends id="" and name=""
             ResponseStreamEvent {
+                id: None,
                 choices: vec![ChoiceDelta {
                     index: 0,
                     delta: Some(ResponseMessageDelta {
@@ -3910,11 +4090,14 @@ mod tests {
                         }]),
                         reasoning_content: None,
                     }),
+                    message: None,
                     finish_reason: None,
                 }],
                 usage: None,
+                system_fingerprint: None,
             },
             ResponseStreamEvent {
+                id: None,
                 choices: vec![ChoiceDelta {
                     index: 0,
                     delta: Some(ResponseMessageDelta {
@@ -3931,18 +4114,23 @@ mod tests {
                         }]),
                         reasoning_content: None,
                     }),
+                    message: None,
                     finish_reason: None,
                 }],
                 usage: None,
+                system_fingerprint: None,
             },
             // Final chunk: finish_reason = "tool_calls"
             ResponseStreamEvent {
+                id: None,
                 choices: vec![ChoiceDelta {
                     index: 0,
                     delta: None,
+                    message: None,
                     finish_reason: Some("tool_calls".into()),
                 }],
                 usage: None,
+                system_fingerprint: None,
             },
         ];
 
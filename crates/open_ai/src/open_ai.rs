@@ -3,7 +3,10 @@ pub mod completion;
 pub mod responses;
 
 use anyhow::{Context as _, Result, anyhow};
-use futures::{AsyncBufReadExt, AsyncReadExt, StreamExt, io::BufReader, stream::BoxStream};
+use collections::HashMap;
+use futures::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, StreamExt, io::BufReader, stream::BoxStream,
+};
 use http_client::{
     AsyncBody, CustomHeaders, HttpClient, Method, Request as HttpRequest, RequestBuilderExt,
     StatusCode,
@@ -441,7 +444,171 @@ impl Model {
 
 #[cfg(test)]
 mod tests {
-    use super::{Model, ReasoningEffort};
+    use super::{
+        MAX_CONSECUTIVE_PARSE_FAILURES, MessageContent, Model, ReasoningEffort, Request,
+        RequestMessage, ResponseStreamEvent, parse_response_stream,
+    };
+    use anyhow::Result;
+    use futures::{StreamExt as _, executor::block_on, io::Cursor};
+
+    fn test_request() -> Request {
+        Request {
+            model: "gpt-4".to_string(),
+            messages: vec![RequestMessage::User {
+                content: MessageContent::Plain("Hello".to_string()),
+            }],
+            stream: true,
+            stream_options: None,
+            max_completion_tokens: None,
+            max_tokens: None,
+            stop: Vec::new(),
+            temperature: None,
+            tool_choice: None,
+            parallel_tool_calls: None,
+            tools: Vec::new(),
+            prompt_cache_key: None,
+            reasoning_effort: None,
+            service_tier: None,
+            user: None,
+            seed: None,
+            logit_bias: None,
+        }
+    }
+
+    fn stream_events(body: String) -> Vec<Result<ResponseStreamEvent>> {
+        block_on(parse_response_stream(Cursor::new(body.into_bytes())).collect())
+    }
+
+    const TEXT_EVENT: &str =
+        r#"data: {"choices":[{"index":0,"delta":{"content":"Hi"},"finish_reason":null}]}"#;
+
+    #[test]
+    fn stream_completion_skips_a_single_malformed_event() {
+        let body = format!("{TEXT_EVENT}\ndata: {{\"choices\": [\n{TEXT_EVENT}\ndata: [DONE]\n");
+
+        let events = stream_events(body);
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.is_ok()));
+    }
+
+    #[test]
+    fn stream_completion_ignores_keep_alive_comments() {
+        let body = format!(": ping\n{TEXT_EVENT}\n\n: ping\n:\n{TEXT_EVENT}\ndata: [DONE]\n");
+
+        let events = stream_events(body);
+
+        assert_eq!(events.len(), 2);
+        assert!(events.iter().all(|event| event.is_ok()));
+    }
+
+    #[test]
+    fn stream_completion_reads_content_from_delta() {
+        let body = format!("{TEXT_EVENT}\ndata: [DONE]\n");
+
+        let events = stream_events(body);
+
+        assert_eq!(events.len(), 1);
+        let event = events[0].as_ref().unwrap();
+        let delta = event.choices[0].delta.as_ref().unwrap();
+        assert_eq!(delta.content.as_deref(), Some("Hi"));
+    }
+
+    #[test]
+    fn stream_completion_reads_content_from_full_message() {
+        let message_event = r#"data: {"choices":[{"index":0,"message":{"role":"assistant","content":"Hello","tool_calls":[{"id":"call_1","type":"function","function":{"name":"a","arguments":"{}"}},{"id":"call_2","type":"function","function":{"name":"b","arguments":"{}"}}]},"finish_reason":"tool_calls"}]}"#;
+        let body = format!("{message_event}\ndata: [DONE]\n");
+
+        let events = stream_events(body);
+
+        assert_eq!(events.len(), 1);
+        let event = events[0].as_ref().unwrap();
+        let message = event.choices[0].message.as_ref().unwrap();
+        assert_eq!(message.content.as_deref(), Some("Hello"));
+        let tool_calls = message.tool_calls.as_ref().unwrap();
+        assert_eq!(
+            tool_calls
+                .iter()
+                .map(|tool_call| (tool_call.index, tool_call.id.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![(0, Some("call_1")), (1, Some("call_2"))]
+        );
+    }
+
+    #[test]
+    fn stream_completion_reads_events_with_both_delta_and_message() {
+        let event = r#"data: {"choices":[{"index":0,"delta":{"content":"Hi"},"message":{"role":"assistant","content":"Hi"},"finish_reason":null}]}"#;
+        let body = format!("{event}\ndata: [DONE]\n");
+
+        let events = stream_events(body);
+
+        assert_eq!(events.len(), 1);
+        let choice = &events[0].as_ref().unwrap().choices[0];
+        assert_eq!(
+            choice.delta.as_ref().unwrap().content.as_deref(),
+            Some("Hi")
+        );
+        assert_eq!(
+            choice.message.as_ref().unwrap().content.as_deref(),
+            Some("Hi")
+        );
+    }
+
+    #[test]
+    fn stream_completion_aborts_after_consecutive_malformed_events() {
+        let mut body = format!("{TEXT_EVENT}\n");
+        for _ in 0..=MAX_CONSECUTIVE_PARSE_FAILURES {
+            body.push_str("data: {\"choices\": [\n");
+        }
+        body.push_str(&format!(
+            "{TEXT_EVENT}\ndata: {{\"choices\": [\ndata: [DONE]\n"
+        ));
+
+        let events = stream_events(body);
+
+        assert_eq!(events.len(), 2, "the stream ends at the error");
+        assert!(events[0].is_ok());
+        assert!(events[1].is_err());
+    }
+
+    #[test]
+    fn request_serializes_user_only_when_present() {
+        let json = serde_json::to_value(test_request()).unwrap();
+        assert!(json.get("user").is_none());
+
+        let request = Request {
+            user: Some("9f86d081884c7d65".to_string()),
+            ..test_request()
+        };
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json["user"], "9f86d081884c7d65");
+    }
+
+    #[test]
+    fn request_serializes_seed_only_when_present() {
+        let json = serde_json::to_value(test_request()).unwrap();
+        assert!(json.get("seed").is_none());
+
+        let request = Request {
+            seed: Some(42),
+            ..test_request()
+        };
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json["seed"], 42);
+    }
+
+    #[test]
+    fn request_serializes_logit_bias_only_when_present() {
+        let json = serde_json::to_value(test_request()).unwrap();
+        assert!(json.get("logit_bias").is_none());
+
+        let request = Request {
+            logit_bias: Some([("50256".to_string(), -100.0)].into_iter().collect()),
+            ..test_request()
+        };
+        let json = serde_json::to_value(request).unwrap();
+        assert_eq!(json["logit_bias"], serde_json::json!({ "50256": -100.0 }));
+    }
 
     #[test]
     fn gpt_5_1_uses_none_reasoning_by_default() {
@@ -555,6 +722,16 @@ pub struct Request {
     pub reasoning_effort: Option<ReasoningEffort>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub service_tier: Option<ServiceTier>,
+    /// A stable identifier for the end user, used by OpenAI for abuse monitoring.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Makes sampling best-effort deterministic across requests with the same seed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Adjusts the likelihood of specific tokens, keyed by token ID in the model's tokenizer.
+    /// Values range from -100 (ban the token) to 100 (force it).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logit_bias: Option<HashMap<String, f32>>,
 }
 
 /// Service tier for OpenAI requests. Maps to the top-level `service_tier`
@@ -712,7 +889,11 @@ pub struct ResponseMessageDelta {
     pub role: Option<Role>,
     pub content: Option<String>,
     pub reasoning: Option<String>,
-    #[serde(default, skip_serializing_if = "is_none_or_empty")]
+    #[serde(
+        default,
+        deserialize_with = "deserialize_tool_call_chunks",
+        skip_serializing_if = "is_none_or_empty"
+    )]
     pub tool_calls: Option<Vec<ToolCallChunk>>,
     #[serde(default, skip_serializing_if = "is_none_or_empty")]
     pub reasoning_content: Option<String>,
@@ -729,6 +910,35 @@ pub struct ToolCallChunk {
     pub function: Option<FunctionChunk>,
 }
 
+/// Tool calls in a complete message have no `index`, so their position in the list is used
+/// instead.
+fn deserialize_tool_call_chunks<'de, D>(
+    deserializer: D,
+) -> Result<Option<Vec<ToolCallChunk>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct RawToolCallChunk {
+        index: Option<usize>,
+        id: Option<String>,
+        function: Option<FunctionChunk>,
+    }
+
+    let chunks = Option::<Vec<RawToolCallChunk>>::deserialize(deserializer)?;
+    Ok(chunks.map(|chunks| {
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(position, chunk)| ToolCallChunk {
+                index: chunk.index.unwrap_or(position),
+                id: chunk.id,
+                function: chunk.function,
+            })
+            .collect()
+    }))
+}
+
 #[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub struct FunctionChunk {
     pub name: Option<String>,
@@ -746,6 +956,10 @@ pub struct Usage {
 pub struct ChoiceDelta {
     pub index: u32,
     pub delta: Option<ResponseMessageDelta>,
+    /// Some OpenAI-compatible servers send a single event holding the complete message under
+    /// `message` instead of streaming `delta`s.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<ResponseMessageDelta>,
     pub finish_reason: Option<String>,
 }
 
@@ -776,8 +990,15 @@ pub enum ResponseStreamResult {
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct ResponseStreamEvent {
+    /// The completion id, repeated on every chunk of the stream.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub choices: Vec<ChoiceDelta>,
     pub usage: Option<Usage>,
+    /// Identifies the backend configuration that served the request. Together with `seed`,
+    /// an unchanged fingerprint indicates that outputs should be reproducible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_fingerprint: Option<String>,
 }
 
 pub async fn non_streaming_completion(
@@ -826,6 +1047,67 @@ pub async fn non_streaming_completion(
     }
 }
 
+/// How many stream events in a row may fail to parse before the stream is aborted. A single
+/// malformed event is skipped, but a run of them means the response can no longer be trusted.
+const MAX_CONSECUTIVE_PARSE_FAILURES: usize = 3;
+
+fn parse_stream_line(
+    line: std::io::Result<String>,
+    consecutive_parse_failures: &mut usize,
+) -> Option<Result<ResponseStreamEvent>> {
+    let line = match line {
+        Ok(line) => line,
+        Err(error) => return Some(Err(anyhow!(error))),
+    };
+    // Lines without a `data` field, such as `: ping` keep-alive comments, carry no event.
+    let line = line
+        .strip_prefix("data: ")
+        .or_else(|| line.strip_prefix("data:"))?;
+    if line == "[DONE]" {
+        return None;
+    }
+    match serde_json::from_str(line) {
+        Ok(ResponseStreamResult::Ok(response)) => {
+            *consecutive_parse_failures = 0;
+            Some(Ok(response))
+        }
+        Ok(ResponseStreamResult::Err { error }) => Some(Err(anyhow!(error.message))),
+        Err(error) => {
+            log::error!(
+                "Failed to parse OpenAI response into ResponseStreamResult: `{}`\n\
+                Response: `{}`",
+                error,
+                line,
+            );
+            *consecutive_parse_failures += 1;
+            if *consecutive_parse_failures > MAX_CONSECUTIVE_PARSE_FAILURES {
+                Some(Err(anyhow!(error).context(format!(
+                    "{consecutive_parse_failures} consecutive OpenAI stream events failed to parse"
+                ))))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+fn parse_response_stream(
+    body: impl AsyncBufRead + Send + 'static,
+) -> BoxStream<'static, Result<ResponseStreamEvent>> {
+    body.lines()
+        .scan((0, false), |(consecutive_parse_failures, aborted), line| {
+            // Nothing after the error reporting too many malformed events is delivered.
+            if *aborted {
+                return futures::future::ready(None);
+            }
+            let event = parse_stream_line(line, consecutive_parse_failures);
+            *aborted = *consecutive_parse_failures > MAX_CONSECUTIVE_PARSE_FAILURES;
+            futures::future::ready(Some(event))
+        })
+        .filter_map(futures::future::ready)
+        .boxed()
+}
+
 pub async fn stream_completion(
     client: &dyn HttpClient,
     provider_name: &str,
@@ -848,37 +1130,7 @@ pub async fn stream_completion(
 
     let mut response = client.send(request).await?;
     if response.status().is_success() {
-        let reader = BufReader::new(response.into_body());
-        Ok(reader
-            .lines()
-            .filter_map(|line| async move {
-                match line {
-                    Ok(line) => {
-                        let line = line.strip_prefix("data: ").or_else(|| line.strip_prefix("data:"))?;
-                        if line == "[DONE]" {
-                            None
-                        } else {
-                            match serde_json::from_str(line) {
-                                Ok(ResponseStreamResult::Ok(response)) => Some(Ok(response)),
-                                Ok(ResponseStreamResult::Err { error }) => {
-                                    Some(Err(anyhow!(error.message)))
-                                }
-                                Err(error) => {
-                                    log::error!(
-                                        "Failed to parse OpenAI response into ResponseStreamResult: `{}`\n\
-                                        Response: `{}`",
-                                        error,
-                                        line,
-                                    );
-                                    Some(Err(anyhow!(error)))
-                                }
-                            }
-                        }
-                    }
-                    Err(error) => Some(Err(anyhow!(error))),
-                }
-            })
-            .boxed())
+        Ok(parse_response_stream(BufReader::new(response.into_body())))
     } else {
         let mut body = String::new();
         response
@@ -112,6 +112,7 @@ pub struct ContentPromptDiagnosticContext {
 pub struct ContentPromptContext {
     pub content_type: String,
     pub language_name: Option<String>,
+    pub file_path: Option<String>,
     pub is_insert: bool,
     pub is_truncated: bool,
     pub document_content: String,
@@ -124,6 +125,7 @@ pub struct ContentPromptContext {
 pub struct ContentPromptContextV2 {
     pub content_type: String,
     pub language_name: Option<String>,
+    pub file_path: Option<String>,
     pub is_truncated: bool,
     pub document_content: String,
     pub rewrite_section: String,
@@ -166,6 +168,58 @@ mod tests {
         assert!(context.skills.is_empty());
     }
 
+    #[gpui::test]
+    fn test_inline_transformation_prompt_truncates_at_char_boundaries(
+        cx: &mut gpui::TestAppContext,
+    ) {
+        // 12,501 four-byte characters put both truncation points 50,000 bytes
+        // from the selection in the middle of a character.
+        let crabs = "🦀".repeat(12_501);
+        let text = format!("{crabs}y selected y{crabs}");
+        let range_start = crabs.len() + 1;
+        let range_end = range_start + " selected ".len();
+        let buffer = cx.new(|cx| language::Buffer::local(text, cx));
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+
+        let prompt = PromptBuilder::new(None)
+            .unwrap()
+            .generate_inline_transformation_prompt(
+                "Rewrite this".to_string(),
+                None,
+                None,
+                snapshot,
+                range_start..range_end,
+            )
+            .unwrap();
+
+        assert!(prompt.contains("y<rewrite_this>\n selected \n</rewrite_this>y"));
+        assert!(prompt.contains("has been truncated"));
+    }
+
+    #[gpui::test]
+    fn test_inline_transformation_prompt_does_not_escape_file_path(cx: &mut gpui::TestAppContext) {
+        let buffer = cx.new(|cx| language::Buffer::local("fn main() {}", cx));
+        let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+        let prompt_builder = PromptBuilder::new(None).unwrap();
+        let file_path = "john's notes/a&b.rs";
+
+        let prompt = prompt_builder
+            .generate_inline_transformation_prompt(
+                "Rewrite this".to_string(),
+                None,
+                Some(file_path),
+                snapshot.clone(),
+                0..2,
+            )
+            .unwrap();
+        assert!(prompt.contains("The file is located at `john's notes/a&b.rs`."));
+
+        let prompt = prompt_builder
+            .generate_inline_transformation_prompt_tools(None, Some(file_path), snapshot, 0..2)
+            .unwrap();
+        assert!(prompt.contains("The file is located at `john's notes/a&b.rs`."));
+    }
+
     // Hidden-skill filtering used to live here, but it's now the
     // responsibility of `select_catalog_skills` in `agent.rs`, which is the
     // single source of truth for which skills enter the catalog.
@@ -358,6 +412,7 @@ impl PromptBuilder {
     pub fn generate_inline_transformation_prompt_tools(
         &self,
         language_name: Option<&LanguageName>,
+        file_path: Option<&str>,
         buffer: BufferSnapshot,
         range: Range<usize>,
     ) -> Result<String, RenderError> {
@@ -419,6 +474,7 @@ impl PromptBuilder {
         let context = ContentPromptContextV2 {
             content_type: content_type.to_string(),
             language_name: language_name.map(|s| s.to_string()),
+            file_path: file_path.map(|s| s.to_string()),
             is_truncated,
             document_content,
             rewrite_section,
@@ -431,6 +487,7 @@ impl PromptBuilder {
         &self,
         user_prompt: String,
         language_name: Option<&LanguageName>,
+        file_path: Option<&str>,
         buffer: BufferSnapshot,
         range: Range<usize>,
     ) -> Result<String, RenderError> {
@@ -502,6 +559,7 @@ impl PromptBuilder {
         let context = ContentPromptContext {
             content_type: content_type.to_string(),
             language_name: language_name.map(|s| s.to_string()),
+            file_path: file_path.map(|s| s.to_string()),
             is_insert,
             is_truncated,
             document_content,
@@ -246,6 +246,16 @@ pub struct AgentSettingsContent {
     ///
     /// Default: true
     pub inline_assistant_use_streaming_tools: Option<bool>,
+    /// Whether to include the path of the file being edited in the inline assistant's prompt.
+    /// Paths of files matching the `private_files` setting are never included.
+    ///
+    /// Default: true
+    pub inline_assistant_include_file_path: Option<bool>,
+    /// How long, in milliseconds, the inline assistant gathers streamed edits before applying
+    /// them to the buffer as a single edit. 0 applies each edit as soon as it arrives.
+    ///
+    /// Default: 16
+    pub inline_assistant_coalesce_ms: Option<u64>,
     /// Model to use for generating git commit messages. Defaults to default_model when not specified.
     pub commit_message_model: Option<LanguageModelSelection>,
     /// Whether to include project rules files (AGENTS.md, CLAUDE.md, .rules, etc.)
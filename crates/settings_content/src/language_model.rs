@@ -382,6 +382,16 @@ pub struct OpenAiSettingsContent {
     pub api_url: Option<String>,
     pub available_models: Option<Vec<OpenAiAvailableModel>>,
     pub custom_headers: Option<HashMap<String, String>>,
+    /// A stable identifier sent as the `user` field of every request, which OpenAI
+    /// uses for abuse monitoring. Use an opaque value (such as a hash), never
+    /// an email address or other personal information.
+    pub user: Option<String>,
+    /// A seed sent with Chat Completions requests so that repeated requests with the
+    /// same parameters return best-effort deterministic results.
+    pub seed: Option<u64>,
+    /// Biases sent with Chat Completions requests, mapping token IDs to values between
+    /// -100 and 100. Token IDs depend on the model's tokenizer.
+    pub logit_bias: Option<HashMap<String, f32>>,
 }
 
 #[with_fallible_options]